@@ -0,0 +1,423 @@
+use crate::{LinkResource, ProseMirrorNode};
+use generational_arena::{Arena, Index};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+
+thread_local! {
+    /// Live JS editor handles, keyed by the `Index` inside an `InstanceHandle`.
+    /// Mirrors the static instance-pool pattern used in wasm frontends, letting multiple
+    /// editors coexist without threading DOM id strings through every command.
+    static INSTANCES: RefCell<Arena<JsValue>> = RefCell::new(Arena::new());
+}
+
+/// An opaque handle to a live TipTap instance, returned by `create` and required by every
+/// other binding in this module instead of the DOM id it was mounted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstanceHandle(Index);
+
+fn with_instance<T>(handle: &InstanceHandle, f: impl FnOnce(&JsValue) -> T) -> T {
+    INSTANCES.with(|instances| {
+        let instances = instances.borrow();
+        let instance = instances
+            .get(handle.0)
+            .expect("InstanceHandle should refer to a live TipTap instance");
+        f(instance)
+    })
+}
+
+/// Bindings into the JS side of this crate (see `js/tiptap.js`), which owns the actual
+/// TipTap/ProseMirror editor instances.
+#[wasm_bindgen(module = "/js/tiptap.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = createTiptap)]
+    fn create_tiptap(
+        id: String,
+        content: String,
+        editable: bool,
+        on_change: &Closure<dyn Fn(String)>,
+        on_selection: &Closure<dyn Fn()>,
+        on_search_results: &Closure<dyn Fn(JsValue)>,
+    ) -> JsValue;
+
+    #[wasm_bindgen(js_name = destroyTiptap)]
+    fn destroy_tiptap(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = getState)]
+    fn get_state_js(instance: &JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_name = toggleHeading)]
+    fn toggle_heading_js(instance: &JsValue, level: u8);
+
+    #[wasm_bindgen(js_name = setParagraph)]
+    fn set_paragraph_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleBold)]
+    fn toggle_bold_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleItalic)]
+    fn toggle_italic_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleStrike)]
+    fn toggle_strike_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleBlockquote)]
+    fn toggle_blockquote_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleHighlight)]
+    fn toggle_highlight_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = setTextAlignLeft)]
+    fn set_text_align_left_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = setTextAlignCenter)]
+    fn set_text_align_center_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = setTextAlignRight)]
+    fn set_text_align_right_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = setTextAlignJustify)]
+    fn set_text_align_justify_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = setImage)]
+    fn set_image_js(instance: &JsValue, url: String, alt: String, title: String);
+
+    #[wasm_bindgen(js_name = toggleBulletList)]
+    fn toggle_bullet_list_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleOrderedList)]
+    fn toggle_ordered_list_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleTaskList)]
+    fn toggle_task_list_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleCodeBlock)]
+    fn toggle_code_block_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = toggleCode)]
+    fn toggle_code_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = search)]
+    fn search_js(instance: &JsValue, query: String, case_sensitive: bool, regex: bool);
+
+    #[wasm_bindgen(js_name = replaceCurrent)]
+    fn replace_current_js(instance: &JsValue, with: String);
+
+    #[wasm_bindgen(js_name = replaceAll)]
+    fn replace_all_js(instance: &JsValue, with: String);
+
+    #[wasm_bindgen(js_name = clearSearch)]
+    fn clear_search_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = nextMatch)]
+    fn next_match_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = prevMatch)]
+    fn prev_match_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = getJson)]
+    fn get_json_js(instance: &JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_name = setJson)]
+    fn set_json_js(instance: &JsValue, json: JsValue);
+
+    #[wasm_bindgen(js_name = setColor)]
+    fn set_color_js(instance: &JsValue, color: Option<String>);
+
+    #[wasm_bindgen(js_name = setHighlightColor)]
+    fn set_highlight_color_js(instance: &JsValue, color: Option<String>);
+
+    #[wasm_bindgen(js_name = unsetColor)]
+    fn unset_color_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = indent)]
+    fn indent_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = outdent)]
+    fn outdent_js(instance: &JsValue);
+
+    #[wasm_bindgen(js_name = setLink)]
+    fn set_link_js(instance: &JsValue, href: String, text: Option<String>, target: Option<String>);
+
+    #[wasm_bindgen(js_name = unsetLink)]
+    fn unset_link_js(instance: &JsValue);
+}
+
+/// Creates a new TipTap instance, mounting it to the element with the given `id`, and returns
+/// the `InstanceHandle` every other binding in this module expects.
+pub fn create(
+    id: String,
+    content: String,
+    editable: bool,
+    on_change: &Closure<dyn Fn(String)>,
+    on_selection: &Closure<dyn Fn()>,
+    on_search_results: &Closure<dyn Fn(JsValue)>,
+) -> InstanceHandle {
+    let instance = create_tiptap(
+        id,
+        content,
+        editable,
+        on_change,
+        on_selection,
+        on_search_results,
+    );
+    let index = INSTANCES.with(|instances| instances.borrow_mut().insert(instance));
+    InstanceHandle(index)
+}
+
+/// Tears down the TipTap instance behind `handle` and removes it from the registry.
+pub fn destroy(handle: InstanceHandle) {
+    let instance = INSTANCES.with(|instances| instances.borrow_mut().remove(handle.0));
+    if let Some(instance) = instance {
+        destroy_tiptap(&instance);
+    }
+}
+
+/// One of the three heading levels supported by the `Heading` extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeadingLevel {
+    H1,
+    H2,
+    H3,
+}
+
+/// The state of every toggleable command at the current selection, reported by the JS side
+/// whenever the editor's selection changes. Used to drive toolbar active/inactive styling.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct State {
+    pub heading: Option<HeadingLevel>,
+    pub paragraph: bool,
+    pub bold: bool,
+    pub italic: bool,
+    pub strike: bool,
+    pub blockquote: bool,
+    pub highlight: bool,
+    pub align_left: bool,
+    pub align_center: bool,
+    pub align_right: bool,
+    pub align_justify: bool,
+    pub bullet_list: bool,
+    pub ordered_list: bool,
+    pub task_list: bool,
+    pub code_block: bool,
+    pub code: bool,
+    pub text_color: Option<String>,
+    pub highlight_color: Option<String>,
+    /// Indent level of the current block, clamped to `0..=8` by the `indent`/`outdent` extension.
+    pub indent: u8,
+    /// Whether the cursor is currently inside a `Link` mark.
+    pub link: bool,
+    /// The `href` of the `Link` mark at the cursor, if any.
+    pub link_href: Option<String>,
+}
+
+/// Reads back the current `State` of the editor behind `handle`.
+pub fn get_state(handle: &InstanceHandle) -> Result<State, serde_wasm_bindgen::Error> {
+    with_instance(handle, |instance| {
+        serde_wasm_bindgen::from_value(get_state_js(instance))
+    })
+}
+
+/// Toggles the given heading level for the current selection.
+pub fn toggle_heading(handle: &InstanceHandle, level: HeadingLevel) {
+    let level = match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+    };
+    with_instance(handle, |instance| toggle_heading_js(instance, level));
+}
+
+/// Sets the current selection's block type to a plain paragraph.
+pub fn set_paragraph(handle: &InstanceHandle) {
+    with_instance(handle, set_paragraph_js);
+}
+
+/// Toggles "Bold" for the current selection.
+pub fn toggle_bold(handle: &InstanceHandle) {
+    with_instance(handle, toggle_bold_js);
+}
+
+/// Toggles "Italic" for the current selection.
+pub fn toggle_italic(handle: &InstanceHandle) {
+    with_instance(handle, toggle_italic_js);
+}
+
+/// Toggles "Strike" for the current selection.
+pub fn toggle_strike(handle: &InstanceHandle) {
+    with_instance(handle, toggle_strike_js);
+}
+
+/// Toggles "Blockquote" for the current selection.
+pub fn toggle_blockquote(handle: &InstanceHandle) {
+    with_instance(handle, toggle_blockquote_js);
+}
+
+/// Toggles "Highlight" for the current selection.
+pub fn toggle_highlight(handle: &InstanceHandle) {
+    with_instance(handle, toggle_highlight_js);
+}
+
+/// Sets text alignment of the current selection to "left".
+pub fn set_text_align_left(handle: &InstanceHandle) {
+    with_instance(handle, set_text_align_left_js);
+}
+
+/// Sets text alignment of the current selection to "center".
+pub fn set_text_align_center(handle: &InstanceHandle) {
+    with_instance(handle, set_text_align_center_js);
+}
+
+/// Sets text alignment of the current selection to "right".
+pub fn set_text_align_right(handle: &InstanceHandle) {
+    with_instance(handle, set_text_align_right_js);
+}
+
+/// Sets text alignment of the current selection to "justify".
+pub fn set_text_align_justify(handle: &InstanceHandle) {
+    with_instance(handle, set_text_align_justify_js);
+}
+
+/// Replaces the current selection with an image.
+pub fn set_image(handle: &InstanceHandle, url: String, alt: String, title: String) {
+    with_instance(handle, |instance| set_image_js(instance, url, alt, title));
+}
+
+/// Toggles "BulletList" for the current selection.
+pub fn toggle_bullet_list(handle: &InstanceHandle) {
+    with_instance(handle, toggle_bullet_list_js);
+}
+
+/// Toggles "OrderedList" for the current selection.
+pub fn toggle_ordered_list(handle: &InstanceHandle) {
+    with_instance(handle, toggle_ordered_list_js);
+}
+
+/// Toggles "TaskList" for the current selection.
+pub fn toggle_task_list(handle: &InstanceHandle) {
+    with_instance(handle, toggle_task_list_js);
+}
+
+/// Toggles "CodeBlock" for the current selection.
+pub fn toggle_code_block(handle: &InstanceHandle) {
+    with_instance(handle, toggle_code_block_js);
+}
+
+/// Toggles inline "Code" for the current selection.
+pub fn toggle_code(handle: &InstanceHandle) {
+    with_instance(handle, toggle_code_js);
+}
+
+/// The outcome of the in-document search plugin, reported whenever the match set changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SearchResults {
+    /// Total number of matches found for the current query.
+    pub total: usize,
+
+    /// Index of the currently active match among `total`, if any.
+    pub active_index: Option<usize>,
+}
+
+/// Parses the `SearchResults` reported by the JS side, falling back to the default (empty)
+/// result set on a deserialization error.
+pub fn parse_search_results(value: JsValue) -> SearchResults {
+    match serde_wasm_bindgen::from_value(value) {
+        Ok(results) => results,
+        Err(err) => {
+            tracing::error!("Could not parse JsValue as TipTap search results. Deserialization error: '{err}'. Falling back to empty results.");
+            Default::default()
+        }
+    }
+}
+
+/// Runs (or re-runs) a search for `query` across the document, installing match decorations.
+pub fn search(handle: &InstanceHandle, query: String, case_sensitive: bool, regex: bool) {
+    with_instance(handle, |instance| {
+        search_js(instance, query, case_sensitive, regex)
+    });
+}
+
+/// Replaces the currently active match with `with` and re-runs the search.
+pub fn replace_current(handle: &InstanceHandle, with: String) {
+    with_instance(handle, |instance| replace_current_js(instance, with));
+}
+
+/// Replaces every match with `with`.
+pub fn replace_all(handle: &InstanceHandle, with: String) {
+    with_instance(handle, |instance| replace_all_js(instance, with));
+}
+
+/// Clears the active search, removing all match decorations.
+pub fn clear_search(handle: &InstanceHandle) {
+    with_instance(handle, clear_search_js);
+}
+
+/// Moves the active match to the next one, wrapping around and scrolling it into view.
+pub fn next_match(handle: &InstanceHandle) {
+    with_instance(handle, next_match_js);
+}
+
+/// Moves the active match to the previous one, wrapping around and scrolling it into view.
+pub fn prev_match(handle: &InstanceHandle) {
+    with_instance(handle, prev_match_js);
+}
+
+/// Reads back the current document as a `ProseMirrorNode` tree (`editor.getJSON()`).
+pub fn get_json(handle: &InstanceHandle) -> Result<ProseMirrorNode, serde_wasm_bindgen::Error> {
+    with_instance(handle, |instance| {
+        serde_wasm_bindgen::from_value(get_json_js(instance))
+    })
+}
+
+/// Replaces the document content with the given `ProseMirrorNode` tree
+/// (`editor.commands.setContent(json)`).
+pub fn set_json(
+    handle: &InstanceHandle,
+    json: &ProseMirrorNode,
+) -> Result<(), serde_wasm_bindgen::Error> {
+    let json = serde_wasm_bindgen::to_value(json)?;
+    with_instance(handle, |instance| set_json_js(instance, json));
+    Ok(())
+}
+
+/// Sets (or, passing `None`, clears) the `Color` mark's text color for the current selection.
+/// The color at the cursor is reported back through `State::text_color`.
+pub fn set_color(handle: &InstanceHandle, color: Option<String>) {
+    with_instance(handle, |instance| set_color_js(instance, color));
+}
+
+/// Sets (or, passing `None`, clears) the `Highlight` mark's color for the current selection.
+/// The color at the cursor is reported back through `State::highlight_color`.
+pub fn set_highlight_color(handle: &InstanceHandle, color: Option<String>) {
+    with_instance(handle, |instance| set_highlight_color_js(instance, color));
+}
+
+/// Clears both the text color and the highlight color for the current selection.
+pub fn unset_color(handle: &InstanceHandle) {
+    with_instance(handle, unset_color_js);
+}
+
+/// Indents the selection's block range: `sinkListItem` inside a list, otherwise increments the
+/// block's clamped `indent` attribute.
+pub fn indent(handle: &InstanceHandle) {
+    with_instance(handle, indent_js);
+}
+
+/// Outdents the selection's block range: `liftListItem` inside a list, otherwise decrements the
+/// block's clamped `indent` attribute.
+pub fn outdent(handle: &InstanceHandle) {
+    with_instance(handle, outdent_js);
+}
+
+/// Creates or edits the `Link` mark across the current selection
+/// (`extendMarkRange('link').setLink(...)`).
+pub fn set_link(handle: &InstanceHandle, link: LinkResource) {
+    with_instance(handle, |instance| {
+        set_link_js(instance, link.href, link.text, link.target)
+    });
+}
+
+/// Removes the `Link` mark from the current selection.
+pub fn unset_link(handle: &InstanceHandle) {
+    with_instance(handle, unset_link_js);
+}
@@ -12,3 +12,25 @@ pub struct ImageResource {
     // Example: https:://my-site.com/public/image.png
     pub url: String,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct LinkResource {
+    // Example: https://my-site.com/public/page
+    pub href: String,
+    // Example: "Read more"
+    pub text: Option<String>,
+    // Example: _blank
+    pub target: Option<String>,
+}
+
+/// A single node of a ProseMirror document tree, mirroring the shape produced by TipTap's
+/// `editor.getJSON()` and accepted by `editor.commands.setContent(json)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProseMirrorNode {
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub attrs: Option<serde_json::Value>,
+    pub content: Option<Vec<ProseMirrorNode>>,
+    pub text: Option<String>,
+    pub marks: Option<Vec<serde_json::Value>>,
+}
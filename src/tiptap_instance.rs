@@ -1,6 +1,9 @@
-use crate::{js_tiptap::State, ImageResource};
+use crate::{
+    js_tiptap::{InstanceHandle, State},
+    ImageResource, LinkResource, ProseMirrorNode,
+};
 use tracing::error;
-use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{prelude::Closure, JsValue};
 use yew::{html::Scope, prelude::*};
 
 use super::js_tiptap;
@@ -14,6 +17,11 @@ pub enum Msg {
     /// It is automatically triggered from the JS tiptap instance whenever its content changed.
     _ContentChanged { content: String },
 
+    /// This is an "internal" event, meaning that it SHOULD NOT BE CREATED MANUALLY.
+    /// It is automatically triggered from the JS tiptap instance whenever the active search's
+    /// match set changed.
+    _SearchResultsChanged { results: SearchResults },
+
     /// Toggles "H1" for the current selection.
     H1,
 
@@ -55,6 +63,64 @@ pub enum Msg {
 
     /// Replace the current selection with an image.
     SetImage(ImageResource),
+
+    /// Toggle "BulletList" for the current selection.
+    BulletList,
+
+    /// Toggle "OrderedList" for the current selection.
+    OrderedList,
+
+    /// Toggle "TaskList" for the current selection.
+    TaskList,
+
+    /// Toggle "CodeBlock" for the current selection.
+    CodeBlock,
+
+    /// Toggle inline "Code" for the current selection.
+    Code,
+
+    /// Searches the document for `query`, highlighting every match.
+    Search {
+        query: String,
+        case_sensitive: bool,
+        regex: bool,
+    },
+
+    /// Replaces the currently active match with `with`.
+    ReplaceCurrent { with: String },
+
+    /// Replaces every match with `with`.
+    ReplaceAll { with: String },
+
+    /// Clears the active search and its highlights.
+    ClearSearch,
+
+    /// Moves the active match forward, wrapping around.
+    NextMatch,
+
+    /// Moves the active match backward, wrapping around.
+    PrevMatch,
+
+    /// Sets (or, passing `None`, clears) the text color for the current selection.
+    SetTextColor(Option<String>),
+
+    /// Sets (or, passing `None`, clears) the highlight color for the current selection.
+    SetHighlightColor(Option<String>),
+
+    /// Clears both the text color and the highlight color for the current selection.
+    UnsetColor,
+
+    /// Indents the current block (sinks a list item, or increments a block's indent level).
+    Indent,
+
+    /// Outdents the current block (lifts a list item, or decrements a block's indent level).
+    Outdent,
+
+    /// Creates or edits the hyperlink across the current selection.
+    SetLink(LinkResource),
+
+    /// Removes the hyperlink from the current selection.
+    UnsetLink,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -64,10 +130,12 @@ pub struct Selection {
 
 pub type SelectionState = js_tiptap::State;
 pub type HeadingLevel = js_tiptap::HeadingLevel;
+pub type SearchResults = js_tiptap::SearchResults;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Content {
     pub content: String,
+    pub content_json: Option<ProseMirrorNode>,
 }
 
 #[derive(Properties, PartialEq)]
@@ -81,12 +149,17 @@ pub struct Props {
     /// Initial content of the editor.
     pub content: String,
 
+    /// Initial content of the editor, given as a ProseMirror document tree.
+    /// Takes precedence over `content` when set.
+    pub content_json: Option<ProseMirrorNode>,
+
     /// If set to true, the tiptap instance becomes un-editable.
     pub disabled: bool,
 
     pub on_link: Callback<Option<Scope<TiptapInstance>>>,
     pub on_selection_change: Option<Callback<Selection>>,
     pub on_content_change: Option<Callback<Content>>,
+    pub on_search_results: Option<Callback<SearchResults>>,
 }
 
 pub struct TiptapInstance {
@@ -99,10 +172,27 @@ pub struct TiptapInstance {
     /// We expect this to be called whenever the SELECTION in the editor changes.
     /// We have to own this closure until the end of this components lifetime.
     on_selection: Closure<dyn Fn()>,
+
+    /// This closure is passed on to the JS tiptap instance.
+    /// We expect this to be called whenever the active search's match set changes.
+    /// We have to own this closure until the end of this components lifetime.
+    on_search_results: Closure<dyn Fn(JsValue)>,
+
+    /// Handle to the live JS tiptap instance, obtained from `js_tiptap::create` once mounted.
+    /// `None` until the first `rendered` call.
+    handle: Option<InstanceHandle>,
 }
 
-fn fetch_selection_state(ctx: &Context<TiptapInstance>) -> State {
-    match js_tiptap::get_state(ctx.props().id.clone()) {
+impl TiptapInstance {
+    fn handle(&self) -> &InstanceHandle {
+        self.handle
+            .as_ref()
+            .expect("commands should only be dispatched after the JS tiptap instance was created")
+    }
+}
+
+fn fetch_selection_state(instance: &TiptapInstance) -> State {
+    match js_tiptap::get_state(instance.handle()) {
         Ok(state) => state,
         Err(err) => {
             error!("Could not parse JsValue as TipTap state. Deserialization error: '{err}'. Falling back to default state.");
@@ -111,6 +201,16 @@ fn fetch_selection_state(ctx: &Context<TiptapInstance>) -> State {
     }
 }
 
+fn fetch_content_json(instance: &TiptapInstance) -> Option<ProseMirrorNode> {
+    match js_tiptap::get_json(instance.handle()) {
+        Ok(json) => Some(json),
+        Err(err) => {
+            error!("Could not parse JsValue as TipTap JSON content. Deserialization error: '{err}'. Falling back to no JSON content.");
+            None
+        }
+    }
+}
+
 impl Component for TiptapInstance {
     type Message = Msg;
     type Properties = Props;
@@ -129,14 +229,27 @@ impl Component for TiptapInstance {
         let selected =
             Closure::wrap(Box::new(move || selection_changed_callback.emit(())) as Box<dyn Fn()>);
 
+        let search_results_changed_callback =
+            ctx.link().callback(|results| Msg::_SearchResultsChanged {
+                results: js_tiptap::parse_search_results(results),
+            });
+        let search_results = Closure::wrap(Box::new(move |results: JsValue| {
+            search_results_changed_callback.emit(results)
+        }) as Box<dyn Fn(JsValue)>);
+
         Self {
             on_change: changed,
             on_selection: selected,
+            on_search_results: search_results,
+            handle: None,
         }
     }
 
     fn destroy(&mut self, ctx: &Context<Self>) {
         ctx.props().on_link.emit(None);
+        if let Some(handle) = self.handle.take() {
+            js_tiptap::destroy(handle);
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -144,78 +257,163 @@ impl Component for TiptapInstance {
             Msg::_SelectionChanged => {
                 if let Some(on_selection_change) = &ctx.props().on_selection_change {
                     on_selection_change.emit(Selection {
-                        state: fetch_selection_state(ctx),
+                        state: fetch_selection_state(self),
                     });
                 }
                 false
             }
             Msg::_ContentChanged { content } => {
                 if let Some(on_content_change) = &ctx.props().on_content_change {
-                    on_content_change.emit(Content { content });
+                    on_content_change.emit(Content {
+                        content_json: fetch_content_json(self),
+                        content,
+                    });
+                }
+                false
+            }
+            Msg::_SearchResultsChanged { results } => {
+                if let Some(on_search_results) = &ctx.props().on_search_results {
+                    on_search_results.emit(results);
                 }
                 false
             }
             Msg::H1 => {
-                js_tiptap::toggle_heading(ctx.props().id.clone(), js_tiptap::HeadingLevel::H1);
+                js_tiptap::toggle_heading(self.handle(), js_tiptap::HeadingLevel::H1);
                 true
             }
             Msg::H2 => {
-                js_tiptap::toggle_heading(ctx.props().id.clone(), js_tiptap::HeadingLevel::H2);
+                js_tiptap::toggle_heading(self.handle(), js_tiptap::HeadingLevel::H2);
                 true
             }
             Msg::H3 => {
-                js_tiptap::toggle_heading(ctx.props().id.clone(), js_tiptap::HeadingLevel::H3);
+                js_tiptap::toggle_heading(self.handle(), js_tiptap::HeadingLevel::H3);
                 true
             }
             Msg::Paragraph => {
-                js_tiptap::set_paragraph(ctx.props().id.clone());
+                js_tiptap::set_paragraph(self.handle());
                 true
             }
             Msg::Bold => {
-                js_tiptap::toggle_bold(ctx.props().id.clone());
+                js_tiptap::toggle_bold(self.handle());
                 true
             }
             Msg::Italic => {
-                js_tiptap::toggle_italic(ctx.props().id.clone());
+                js_tiptap::toggle_italic(self.handle());
                 true
             }
             Msg::Strike => {
-                js_tiptap::toggle_strike(ctx.props().id.clone());
+                js_tiptap::toggle_strike(self.handle());
                 true
             }
             Msg::Blockquote => {
-                js_tiptap::toggle_blockquote(ctx.props().id.clone());
+                js_tiptap::toggle_blockquote(self.handle());
                 true
             }
             Msg::Highlight => {
-                js_tiptap::toggle_highlight(ctx.props().id.clone());
+                js_tiptap::toggle_highlight(self.handle());
                 true
             }
             Msg::AlignLeft => {
-                js_tiptap::set_text_align_left(ctx.props().id.clone());
+                js_tiptap::set_text_align_left(self.handle());
                 true
             }
             Msg::AlignCenter => {
-                js_tiptap::set_text_align_center(ctx.props().id.clone());
+                js_tiptap::set_text_align_center(self.handle());
                 true
             }
             Msg::AlignRight => {
-                js_tiptap::set_text_align_right(ctx.props().id.clone());
+                js_tiptap::set_text_align_right(self.handle());
                 true
             }
             Msg::AlignJustify => {
-                js_tiptap::set_text_align_justify(ctx.props().id.clone());
+                js_tiptap::set_text_align_justify(self.handle());
                 true
             }
             Msg::SetImage(resource) => {
                 js_tiptap::set_image(
-                    ctx.props().id.clone(),
+                    self.handle(),
                     resource.url.clone(),
                     resource.alt.clone(),
                     resource.title.clone(),
                 );
                 true
             }
+            Msg::BulletList => {
+                js_tiptap::toggle_bullet_list(self.handle());
+                true
+            }
+            Msg::OrderedList => {
+                js_tiptap::toggle_ordered_list(self.handle());
+                true
+            }
+            Msg::TaskList => {
+                js_tiptap::toggle_task_list(self.handle());
+                true
+            }
+            Msg::CodeBlock => {
+                js_tiptap::toggle_code_block(self.handle());
+                true
+            }
+            Msg::Code => {
+                js_tiptap::toggle_code(self.handle());
+                true
+            }
+            Msg::Search {
+                query,
+                case_sensitive,
+                regex,
+            } => {
+                js_tiptap::search(self.handle(), query, case_sensitive, regex);
+                false
+            }
+            Msg::ReplaceCurrent { with } => {
+                js_tiptap::replace_current(self.handle(), with);
+                true
+            }
+            Msg::ReplaceAll { with } => {
+                js_tiptap::replace_all(self.handle(), with);
+                true
+            }
+            Msg::ClearSearch => {
+                js_tiptap::clear_search(self.handle());
+                false
+            }
+            Msg::NextMatch => {
+                js_tiptap::next_match(self.handle());
+                false
+            }
+            Msg::PrevMatch => {
+                js_tiptap::prev_match(self.handle());
+                false
+            }
+            Msg::SetTextColor(color) => {
+                js_tiptap::set_color(self.handle(), color);
+                true
+            }
+            Msg::SetHighlightColor(color) => {
+                js_tiptap::set_highlight_color(self.handle(), color);
+                true
+            }
+            Msg::UnsetColor => {
+                js_tiptap::unset_color(self.handle());
+                true
+            }
+            Msg::Indent => {
+                js_tiptap::indent(self.handle());
+                true
+            }
+            Msg::Outdent => {
+                js_tiptap::outdent(self.handle());
+                true
+            }
+            Msg::SetLink(link) => {
+                js_tiptap::set_link(self.handle(), link);
+                true
+            }
+            Msg::UnsetLink => {
+                js_tiptap::unset_link(self.handle());
+                true
+            }
         }
     }
 
@@ -227,13 +425,20 @@ impl Component for TiptapInstance {
 
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
         if first_render {
-            js_tiptap::create(
+            self.handle = Some(js_tiptap::create(
                 ctx.props().id.clone(),
                 ctx.props().content.clone(),
                 !ctx.props().disabled,
                 &self.on_change,
                 &self.on_selection,
-            );
+                &self.on_search_results,
+            ));
+
+            if let Some(content_json) = &ctx.props().content_json {
+                if let Err(err) = js_tiptap::set_json(self.handle(), content_json) {
+                    error!("Could not apply initial `content_json`. Serialization error: '{err}'.");
+                }
+            }
 
             // NOTE: Linking is deferred until tiptap instance is known to be ready!
             // The user of this library would otherwise be able to send messages like `H1` before the instance was even created which would only lead to errors.